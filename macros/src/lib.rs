@@ -4,46 +4,215 @@ use syn::parse::{Parse, ParseStream};
 use syn::{parse_macro_input, FnArg, ItemFn, LitStr, PatType, ReturnType, Type};
 
 /// Parse route attribute arguments
-/// Example: POST "/account/register"
+///
+/// The method and path are positional; any additional metadata follows as
+/// comma-separated `key = value` pairs, `key(...)` lists such as `responses(201, 404)`,
+/// or bare flags.
+///
+/// Example: `POST "/account/register", operation_id = "register", tags = ["account"], deprecated`
 struct RouteArgs {
     method: syn::Ident,
     path: LitStr,
+    operation_id: Option<String>,
+    tags: Vec<String>,
+    deprecated: bool,
+    responses: Vec<u16>,
+    security: Option<String>,
 }
 
 impl Parse for RouteArgs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let method: syn::Ident = input.parse()?;
         let path: LitStr = input.parse()?;
-        Ok(Self { method, path })
+
+        let mut operation_id = None;
+        let mut tags = Vec::new();
+        let mut deprecated = false;
+        let mut responses = Vec::new();
+        let mut security = None;
+
+        while !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key: syn::Ident = input.parse()?;
+            match key.to_string().as_str() {
+                "operation_id" => {
+                    input.parse::<syn::Token![=]>()?;
+                    operation_id = Some(input.parse::<LitStr>()?.value());
+                }
+                "tags" => {
+                    input.parse::<syn::Token![=]>()?;
+                    let content;
+                    syn::bracketed!(content in input);
+                    let items = content
+                        .parse_terminated(<LitStr as Parse>::parse, syn::Token![,])?;
+                    tags = items.into_iter().map(|lit| lit.value()).collect();
+                }
+                "deprecated" => {
+                    deprecated = true;
+                }
+                "responses" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let items = content
+                        .parse_terminated(<syn::LitInt as Parse>::parse, syn::Token![,])?;
+                    responses = items
+                        .into_iter()
+                        .map(|lit| lit.base10_parse::<u16>())
+                        .collect::<syn::Result<_>>()?;
+                }
+                "security" => {
+                    input.parse::<syn::Token![=]>()?;
+                    security = Some(input.parse::<LitStr>()?.value());
+                }
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        format!("unknown route argument `{other}`"),
+                    ));
+                }
+            }
+        }
+
+        Ok(Self {
+            method,
+            path,
+            operation_id,
+            tags,
+            deprecated,
+            responses,
+            security,
+        })
     }
 }
 
-/// Extract request body type from function signature
-fn extract_request_type(fn_item: &ItemFn) -> Option<Type> {
+/// Extract the `#[doc = "..."]` lines from a handler, returning its summary (the
+/// first line, trimmed) and description (the remaining lines joined together).
+fn extract_doc_comment(fn_item: &ItemFn) -> (Option<String>, Option<String>) {
+    let mut lines = Vec::new();
+    for attr in &fn_item.attrs {
+        if attr.path().is_ident("doc") {
+            if let syn::Meta::NameValue(nv) = &attr.meta {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(doc),
+                    ..
+                }) = &nv.value
+                {
+                    lines.push(doc.value().trim().to_string());
+                }
+            }
+        }
+    }
+
+    if lines.is_empty() {
+        return (None, None);
+    }
+
+    let summary = lines.first().cloned();
+    let description = if lines.len() > 1 {
+        let rest = lines[1..].join("\n");
+        let rest = rest.trim().to_string();
+        (!rest.is_empty()).then_some(rest)
+    } else {
+        None
+    };
+
+    (summary, description)
+}
+
+/// Typed body extractors and the media type they map to.
+///
+/// Extend this table to teach the macro a new `extractor -> media type` pairing; both
+/// the request-body and schema-registration paths read from it rather than hard-coding
+/// `application/json`.
+const TYPED_BODY_EXTRACTORS: &[(&str, &str)] = &[
+    ("Json", "application/json"),
+    ("Form", "application/x-www-form-urlencoded"),
+];
+
+/// Opaque binary body extractors and the media type they map to. These carry no inner
+/// `ToSchema` type, so they are described with the shared binary schema instead.
+const BINARY_BODY_EXTRACTORS: &[(&str, &str)] = &[
+    ("Multipart", "multipart/form-data"),
+    ("Bytes", "application/octet-stream"),
+];
+
+/// Return types that describe an opaque binary/stream response.
+const BINARY_RESPONSE_TYPES: &[(&str, &str)] = &[
+    ("Bytes", "application/octet-stream"),
+    ("Body", "application/octet-stream"),
+];
+
+/// The request body of a handler, resolved from its extractor.
+enum BodySpec {
+    /// A `ToSchema` body (`Json<T>`, `Form<T>`) served with the given media type.
+    Typed {
+        ty: Type,
+        media_type: &'static str,
+    },
+    /// An opaque binary body (`Multipart`, `Bytes`) served with the given media type.
+    Binary { media_type: &'static str },
+}
+
+/// Return the outermost path-segment identifier of a typed argument, as a string.
+fn arg_type_ident(ty: &Type) -> Option<String> {
+    if let Type::Path(type_path) = ty {
+        return type_path.path.segments.last().map(|s| s.ident.to_string());
+    }
+    None
+}
+
+/// Resolve the request body extractor, if any, to a [`BodySpec`].
+fn extract_request_body(fn_item: &ItemFn) -> Option<BodySpec> {
     for arg in &fn_item.sig.inputs {
         if let FnArg::Typed(PatType { ty, .. }) = arg {
-            if let Type::Path(type_path) = &**ty {
-                let segment = type_path.path.segments.last()?;
-                if segment.ident == "Json" {
-                    if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
-                        if let Some(syn::GenericArgument::Type(inner_type)) = args.args.first() {
-                            return Some(inner_type.clone());
-                        }
-                    }
+            let Some(ident) = arg_type_ident(ty) else {
+                continue;
+            };
+            if let Some((wrapper, media_type)) = TYPED_BODY_EXTRACTORS
+                .iter()
+                .find(|(wrapper, _)| *wrapper == ident)
+            {
+                if let Some(inner) = extract_wrapped_type(fn_item, wrapper) {
+                    return Some(BodySpec::Typed {
+                        ty: inner,
+                        media_type,
+                    });
                 }
             }
+            if let Some((_, media_type)) = BINARY_BODY_EXTRACTORS
+                .iter()
+                .find(|(wrapper, _)| *wrapper == ident)
+            {
+                return Some(BodySpec::Binary { media_type });
+            }
         }
     }
     None
 }
 
-/// Extract query parameter type from function signature
-fn extract_query_type(fn_item: &ItemFn) -> Option<Type> {
+/// Detect an opaque binary/stream response, returning its media type.
+fn extract_binary_response(fn_item: &ItemFn) -> Option<&'static str> {
+    if let ReturnType::Type(_, ty) = &fn_item.sig.output {
+        let ident = arg_type_ident(ty)?;
+        return BINARY_RESPONSE_TYPES
+            .iter()
+            .find(|(name, _)| *name == ident)
+            .map(|(_, media_type)| *media_type);
+    }
+    None
+}
+
+/// Extract the inner type of the first argument whose outermost path segment
+/// matches `wrapper` (e.g. `Query`, `Path`, `Json`).
+fn extract_wrapped_type(fn_item: &ItemFn, wrapper: &str) -> Option<Type> {
     for arg in &fn_item.sig.inputs {
         if let FnArg::Typed(PatType { ty, .. }) = arg {
             if let Type::Path(type_path) = &**ty {
                 let segment = type_path.path.segments.last()?;
-                if segment.ident == "Query" {
+                if segment.ident == wrapper {
                     if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
                         if let Some(syn::GenericArgument::Type(inner_type)) = args.args.first() {
                             return Some(inner_type.clone());
@@ -56,6 +225,37 @@ fn extract_query_type(fn_item: &ItemFn) -> Option<Type> {
     None
 }
 
+/// Extract query parameter type from function signature
+fn extract_query_type(fn_item: &ItemFn) -> Option<Type> {
+    extract_wrapped_type(fn_item, "Query")
+}
+
+/// Extract path parameter type from function signature
+fn extract_path_type(fn_item: &ItemFn) -> Option<Type> {
+    extract_wrapped_type(fn_item, "Path")
+}
+
+/// Extract header parameter type from a `TypedHeader<T>` extractor
+fn extract_header_type(fn_item: &ItemFn) -> Option<Type> {
+    extract_wrapped_type(fn_item, "TypedHeader")
+}
+
+/// Collect the `{name}` placeholders declared in a route path literal.
+fn extract_path_placeholders(path: &str) -> Vec<String> {
+    let mut placeholders = Vec::new();
+    let mut rest = path;
+    while let Some(open) = rest.find('{') {
+        rest = &rest[open + 1..];
+        if let Some(close) = rest.find('}') {
+            placeholders.push(rest[..close].to_string());
+            rest = &rest[close + 1..];
+        } else {
+            break;
+        }
+    }
+    placeholders
+}
+
 /// Check if a type is the unit type ()
 fn is_unit_type(ty: &Type) -> bool {
     matches!(ty, Type::Tuple(tuple) if tuple.elems.is_empty())
@@ -110,25 +310,160 @@ fn extract_response_type(fn_item: &ItemFn) -> Option<Type> {
     None
 }
 
+/// Extract the error type `E` from a `Result<Json<T>, E>` return type.
+fn extract_error_type(fn_item: &ItemFn) -> Option<Type> {
+    if let ReturnType::Type(_, ty) = &fn_item.sig.output {
+        if let Type::Path(type_path) = &**ty {
+            let segment = type_path.path.segments.last()?;
+            if segment.ident == "Result" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(err_type)) = args.args.iter().nth(1) {
+                        return Some(err_type.clone());
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Decide whether an error type should contribute its own `ToSchema` body.
+///
+/// Common transport error types (`StatusCode`, `anyhow::Error`, `String`, …) do not
+/// implement `ToSchema`, so for those we fall back to the generic `ErrorResponse`
+/// reference rather than requiring a schema the type cannot provide.
+fn error_type_has_schema(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path.path.segments.last().is_some_and(|segment| {
+            !matches!(
+                segment.ident.to_string().as_str(),
+                "StatusCode" | "Error" | "BoxError" | "String"
+            )
+        }),
+        _ => false,
+    }
+}
+
+/// Collect every recognized request body extractor present on the handler.
+///
+/// More than one means the handler declares conflicting bodies (e.g. both `Json` and
+/// `Form`), which the macro cannot represent as a single request body.
+fn collect_body_extractors(fn_item: &ItemFn) -> Vec<String> {
+    let mut found = Vec::new();
+    for arg in &fn_item.sig.inputs {
+        if let FnArg::Typed(PatType { ty, .. }) = arg {
+            if let Some(ident) = arg_type_ident(ty) {
+                if TYPED_BODY_EXTRACTORS
+                    .iter()
+                    .chain(BINARY_BODY_EXTRACTORS)
+                    .any(|(wrapper, _)| *wrapper == ident)
+                {
+                    found.push(ident);
+                }
+            }
+        }
+    }
+    found
+}
+
 /// Attribute macro for marking route handlers
-/// Usage: #[route(POST "/account/register")]
+/// Usage: `#[route(POST "/account/register")]`
 ///
-/// # Panics
-/// Panics if an unsupported HTTP method is provided
+/// Invalid methods, malformed paths, and unrepresentable extractor combinations are
+/// reported as located compiler diagnostics pointing at the offending token.
 #[proc_macro_attribute]
-#[allow(clippy::too_many_lines, clippy::option_if_let_else)]
 pub fn route(args: TokenStream, input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(args as RouteArgs);
     let fn_item = parse_macro_input!(input as ItemFn);
 
+    match route_impl(&args, fn_item) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+#[allow(clippy::too_many_lines, clippy::option_if_let_else)]
+fn route_impl(args: &RouteArgs, fn_item: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
     let method = args.method.to_string().to_lowercase();
     let path = args.path.value();
     let fn_name = &fn_item.sig.ident;
 
+    // The path must be an absolute template; a missing leading slash never routes.
+    if !path.starts_with('/') {
+        return Err(syn::Error::new_spanned(
+            &args.path,
+            "route path must start with `/`",
+        ));
+    }
+
+    // A handler can declare at most one request body.
+    let body_extractors = collect_body_extractors(&fn_item);
+    if body_extractors.len() > 1 {
+        return Err(syn::Error::new_spanned(
+            &fn_item.sig.inputs,
+            format!(
+                "a handler may declare at most one request body, found {body_extractors:?}",
+            ),
+        ));
+    }
+
     // Extract types from function signature
-    let request_type = extract_request_type(&fn_item);
+    let body_spec = extract_request_body(&fn_item);
     let query_type = extract_query_type(&fn_item);
+    let path_type = extract_path_type(&fn_item);
+    let header_type = extract_header_type(&fn_item);
     let response_type = extract_response_type(&fn_item);
+    let binary_response = extract_binary_response(&fn_item);
+    // Only treat the error type as a documented body when it can actually provide a schema.
+    let error_type = extract_error_type(&fn_item).filter(error_type_has_schema);
+
+    // A `Path<T>` extractor must line up with the `{name}` placeholders in the route
+    // template; report the common mismatches at compile time rather than emitting a
+    // spec whose path parameters silently disagree with the URL.
+    //
+    // Per-field name matching is not possible here: a `Path<T>` over a named struct
+    // only exposes the type path, not its fields, to the proc-macro. The one arity we
+    // *can* check is a tuple `Path<(A, B, ...)>`, whose element count must equal the
+    // number of placeholders; struct/newtype `Path<T>` is checked for presence only.
+    let placeholders = extract_path_placeholders(&path);
+    if let Some(Type::Tuple(tuple)) = &path_type {
+        if tuple.elems.len() != placeholders.len() {
+            return Err(syn::Error::new_spanned(
+                &args.path,
+                format!(
+                    "tuple `Path` extractor has {} element(s) but the route template declares {} placeholder(s) {:?}",
+                    tuple.elems.len(),
+                    placeholders.len(),
+                    placeholders,
+                ),
+            ));
+        }
+    }
+    if path_type.is_some() && placeholders.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &args.path,
+            "handler takes a `Path<T>` extractor but the route template has no `{name}` placeholders",
+        ));
+    }
+    if path_type.is_none() && !placeholders.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &args.path,
+            format!(
+                "route template declares path placeholders {placeholders:?} but the handler has no `Path<T>` extractor",
+            ),
+        ));
+    }
+
+    // The HTTP method must be one of the verbs axum exposes; report the exact token.
+    if !matches!(
+        method.as_str(),
+        "get" | "post" | "put" | "delete" | "patch"
+    ) {
+        return Err(syn::Error::new_spanned(
+            &args.method,
+            format!("Unsupported HTTP method: {method}"),
+        ));
+    }
 
     // Generate routing method
     let route_method = match method.as_str() {
@@ -137,20 +472,29 @@ pub fn route(args: TokenStream, input: TokenStream) -> TokenStream {
         "put" => quote! { axum::routing::put },
         "delete" => quote! { axum::routing::delete },
         "patch" => quote! { axum::routing::patch },
-        _ => panic!("Unsupported HTTP method: {method}"),
+        _ => unreachable!("method validated above"),
     };
 
     // Generate schema collection
     let mut schema_types = Vec::new();
-    if let Some(req_type) = &request_type {
-        schema_types.push(req_type.clone());
+    if let Some(BodySpec::Typed { ty, .. }) = &body_spec {
+        schema_types.push(ty.clone());
     }
     if let Some(query_type) = &query_type {
         schema_types.push(query_type.clone());
     }
+    if let Some(path_type) = &path_type {
+        schema_types.push(path_type.clone());
+    }
+    if let Some(header_type) = &header_type {
+        schema_types.push(header_type.clone());
+    }
     if let Some(resp_type) = &response_type {
         schema_types.push(resp_type.clone());
     }
+    if let Some(error_type) = &error_type {
+        schema_types.push(error_type.clone());
+    }
 
     let schemas_fn = if schema_types.is_empty() {
         quote! {
@@ -178,124 +522,220 @@ pub fn route(args: TokenStream, input: TokenStream) -> TokenStream {
 
     let meta_mod_name = format_ident!("__{}_meta", fn_name);
 
-    // Build OpenAPI operation directly
-    let operation_builder = if let Some(req_type) = &request_type {
-        if let Some(resp_type) = &response_type {
+    // Build the OpenAPI operation by layering one fragment per recognized extractor /
+    // return type onto a single `OperationBuilder`. Each fragment is a no-op when the
+    // corresponding extractor is absent, so the combinations compose freely.
+    let request_body_fragment = match &body_spec {
+        Some(BodySpec::Typed { ty, media_type }) => quote! {
+            op = op.request_body(Some(
+                utoipa::openapi::request_body::RequestBodyBuilder::new()
+                    .content(
+                        #media_type,
+                        utoipa::openapi::ContentBuilder::new()
+                            .schema(Some(<#ty as utoipa::PartialSchema>::schema()))
+                            .build(),
+                    )
+                    .build(),
+            ));
+        },
+        Some(BodySpec::Binary { media_type }) => quote! {
+            op = op.request_body(Some(
+                utoipa::openapi::request_body::RequestBodyBuilder::new()
+                    .content(
+                        #media_type,
+                        utoipa::openapi::ContentBuilder::new()
+                            .schema(Some(food_openapi_rs::binary_schema()))
+                            .build(),
+                    )
+                    .build(),
+            ));
+        },
+        None => quote! {},
+    };
+
+    let query_params_fragment = if let Some(query_type) = &query_type {
+        quote! {
+            op = op.parameters(Some(<#query_type as utoipa::IntoParams>::into_params(|| None)));
+        }
+    } else {
+        quote! {}
+    };
+
+    // `Path<T>` reuses `IntoParams` like the query branch, but every parameter is forced
+    // into the path and marked required, matching how the `{name}` placeholders behave.
+    let path_params_fragment = if let Some(path_type) = &path_type {
+        quote! {
+            op = op.parameters(Some(
+                <#path_type as utoipa::IntoParams>::into_params(|| None)
+                    .into_iter()
+                    .map(|mut parameter| {
+                        parameter.parameter_in = utoipa::openapi::path::ParameterIn::Path;
+                        parameter.required = utoipa::openapi::Required::True;
+                        parameter
+                    })
+                    .collect::<Vec<_>>(),
+            ));
+        }
+    } else {
+        quote! {}
+    };
+
+    let header_params_fragment = if let Some(header_type) = &header_type {
+        quote! {
+            op = op.parameters(Some(
+                <#header_type as utoipa::IntoParams>::into_params(|| None)
+                    .into_iter()
+                    .map(|mut parameter| {
+                        parameter.parameter_in = utoipa::openapi::path::ParameterIn::Header;
+                        parameter
+                    })
+                    .collect::<Vec<_>>(),
+            ));
+        }
+    } else {
+        quote! {}
+    };
+
+    // The success status defaults to 200; an explicit `responses(...)` list lets the
+    // author declare the real success code (e.g. 201/204) and any extra error codes.
+    // The first listed code is the success code; the rest are further documented
+    // responses (handled per status class below).
+    let (success_status, error_statuses) = if let Some((first, rest)) = args.responses.split_first() {
+        (
+            first.to_string(),
+            rest.iter().map(u16::to_string).collect::<Vec<_>>(),
+        )
+    } else {
+        ("200".to_string(), vec!["400".to_string(), "500".to_string()])
+    };
+
+    // Build a success-shaped response (binary, typed JSON body, or empty) for `status`.
+    let success_response_for = |status: &str| {
+        if let Some(media_type) = binary_response {
             quote! {
-                {
-                    use super::*;
-                    utoipa::openapi::path::OperationBuilder::new()
-                        .request_body(Some(utoipa::openapi::request_body::RequestBodyBuilder::new()
-                            .content(
-                                "application/json",
-                                utoipa::openapi::ContentBuilder::new()
-                                    .schema(Some(<#req_type as utoipa::PartialSchema>::schema()))
-                                    .build()
-                            )
-                            .build()))
-                        .response(
-                            "200",
-                            utoipa::openapi::ResponseBuilder::new()
-                                .description("")
-                                .content(
-                                    "application/json",
-                                    utoipa::openapi::ContentBuilder::new()
-                                        .schema(Some(<#resp_type as utoipa::PartialSchema>::schema()))
-                                        .build()
-                                )
-                                .build()
+                op = op.response(
+                    #status,
+                    utoipa::openapi::ResponseBuilder::new()
+                        .description("")
+                        .content(
+                            #media_type,
+                            utoipa::openapi::ContentBuilder::new()
+                                .schema(Some(food_openapi_rs::binary_schema()))
+                                .build(),
                         )
-                        .response("400", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                        .response("500", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                        .build()
-                }
-            }
-        } else {
-            quote! {
-                {
-                    use super::*;
-                    utoipa::openapi::path::OperationBuilder::new()
-                        .request_body(Some(utoipa::openapi::request_body::RequestBodyBuilder::new()
-                            .content(
-                                "application/json",
-                                utoipa::openapi::ContentBuilder::new()
-                                    .schema(Some(<#req_type as utoipa::PartialSchema>::schema()))
-                                    .build()
-                            )
-                            .build()))
-                        .response("200", utoipa::openapi::ResponseBuilder::new().description("").build())
-                        .response("400", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                        .response("500", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                        .build()
-                }
+                        .build(),
+                );
             }
-        }
-    } else if let Some(query_type) = &query_type {
-        if let Some(resp_type) = &response_type {
+        } else if let Some(resp_type) = &response_type {
             quote! {
-                {
-                    use super::*;
-                    utoipa::openapi::path::OperationBuilder::new()
-                        .parameters(Some(<#query_type as utoipa::IntoParams>::into_params(|| None)))
-                        .response(
-                            "200",
-                            utoipa::openapi::ResponseBuilder::new()
-                                .description("")
-                                .content(
-                                    "application/json",
-                                    utoipa::openapi::ContentBuilder::new()
-                                        .schema(Some(<#resp_type as utoipa::PartialSchema>::schema()))
-                                        .build()
-                                )
-                                .build()
+                op = op.response(
+                    #status,
+                    utoipa::openapi::ResponseBuilder::new()
+                        .description("")
+                        .content(
+                            "application/json",
+                            utoipa::openapi::ContentBuilder::new()
+                                .schema(Some(<#resp_type as utoipa::PartialSchema>::schema()))
+                                .build(),
                         )
-                        .response("400", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                        .response("500", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                        .build()
-                }
+                        .build(),
+                );
             }
         } else {
             quote! {
-                {
-                    use super::*;
-                    utoipa::openapi::path::OperationBuilder::new()
-                        .parameters(Some(<#query_type as utoipa::IntoParams>::into_params(|| None)))
-                        .response("200", utoipa::openapi::ResponseBuilder::new().description("").build())
-                        .response("400", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                        .response("500", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                        .build()
-                }
+                op = op.response(#status, utoipa::openapi::ResponseBuilder::new().description("").build());
             }
         }
-    } else if let Some(resp_type) = &response_type {
-        quote! {
-            {
-                use super::*;
-                utoipa::openapi::path::OperationBuilder::new()
-                    .response(
-                        "200",
+    };
+
+    let success_response_fragment = success_response_for(&success_status);
+
+    // Additional 2xx codes describe further success shapes and reuse the success schema;
+    // everything else is a documented error carrying the typed `E` body (when it
+    // implements `ToSchema`) or the shared `ErrorResponse` component reference.
+    let error_response_fragments: Vec<proc_macro2::TokenStream> = error_statuses
+        .iter()
+        .map(|status| {
+            if status.starts_with('2') {
+                return success_response_for(status);
+            }
+            if let Some(error_type) = &error_type {
+                quote! {
+                    op = op.response(
+                        #status,
                         utoipa::openapi::ResponseBuilder::new()
                             .description("")
                             .content(
                                 "application/json",
                                 utoipa::openapi::ContentBuilder::new()
-                                    .schema(Some(<#resp_type as utoipa::PartialSchema>::schema()))
-                                    .build()
+                                    .schema(Some(<#error_type as utoipa::PartialSchema>::schema()))
+                                    .build(),
                             )
-                            .build()
-                    )
-                    .response("400", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                    .response("500", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                    .build()
+                            .build(),
+                    );
+                }
+            } else {
+                quote! {
+                    op = op.response(#status, utoipa::openapi::Ref::from_response_name("ErrorResponse"));
+                }
             }
-        }
+        })
+        .collect();
+
+    // Human-facing metadata: summary/description from the handler's doc comment,
+    // an operation id (explicit override or the function name), tags, and deprecation.
+    let (summary, description) = extract_doc_comment(&fn_item);
+    let operation_id = args
+        .operation_id
+        .clone()
+        .unwrap_or_else(|| fn_name.to_string());
+
+    let summary_fragment = match summary {
+        Some(summary) => quote! { op = op.summary(Some(#summary)); },
+        None => quote! {},
+    };
+    let description_fragment = match description {
+        Some(description) => quote! { op = op.description(Some(#description)); },
+        None => quote! {},
+    };
+    let tags = &args.tags;
+    let tags_fragment = if tags.is_empty() {
+        quote! {}
     } else {
-        quote! {
-            utoipa::openapi::path::OperationBuilder::new()
-                .response("200", utoipa::openapi::ResponseBuilder::new().description("").build())
-                .response("400", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                .response("500", utoipa::openapi::Ref::from_response_name("ErrorResponse"))
-                .build()
+        quote! { op = op.tags(Some(vec![#(#tags.to_string()),*])); }
+    };
+    let deprecated_fragment = if args.deprecated {
+        quote! { op = op.deprecated(Some(utoipa::openapi::Deprecated::True)); }
+    } else {
+        quote! {}
+    };
+    let security_fragment = match &args.security {
+        Some(scheme) => quote! {
+            op = op.security(utoipa::openapi::security::SecurityRequirement::new(
+                #scheme,
+                Vec::<&str>::new(),
+            ));
+        },
+        None => quote! {},
+    };
+
+    let operation_builder = quote! {
+        {
+            use super::*;
+            let mut op = utoipa::openapi::path::OperationBuilder::new()
+                .operation_id(Some(#operation_id));
+            #summary_fragment
+            #description_fragment
+            #tags_fragment
+            #deprecated_fragment
+            #security_fragment
+            #request_body_fragment
+            #query_params_fragment
+            #path_params_fragment
+            #header_params_fragment
+            #success_response_fragment
+            #(#error_response_fragments)*
+            op.build()
         }
     };
 
@@ -305,7 +745,7 @@ pub fn route(args: TokenStream, input: TokenStream) -> TokenStream {
         "put" => quote! { utoipa::openapi::path::HttpMethod::Put },
         "delete" => quote! { utoipa::openapi::path::HttpMethod::Delete },
         "patch" => quote! { utoipa::openapi::path::HttpMethod::Patch },
-        _ => panic!("Unsupported HTTP method: {method}"),
+        _ => unreachable!("method validated above"),
     };
 
     let path_item_builder = quote! {
@@ -340,5 +780,5 @@ pub fn route(args: TokenStream, input: TokenStream) -> TokenStream {
         }
     };
 
-    TokenStream::from(expanded)
+    Ok(expanded)
 }
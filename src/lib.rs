@@ -1,8 +1,12 @@
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use utoipa::openapi::{
     path::PathItem, Components, OpenApi, OpenApiBuilder, PathsBuilder, RefOr, Response,
 };
 
+mod validation;
+
+pub use validation::{ValidationError, ValidationLayer, ValidationService, Violation};
+
 /// Re-export inventory for macro use
 #[doc(hidden)]
 pub use inventory;
@@ -41,6 +45,42 @@ pub struct SchemaProvider {
 
 inventory::collect!(SchemaProvider);
 
+/// Security scheme provider that contributes an entry to `components.securitySchemes`
+///
+/// Mirrors [`SchemaProvider`]: register one per authentication method so that every
+/// `security = "..."` requirement attached to a route resolves to a documented scheme.
+pub struct SecuritySchemeProvider {
+    pub name: &'static str,
+    pub scheme: fn() -> utoipa::openapi::security::SecurityScheme,
+}
+
+inventory::collect!(SecuritySchemeProvider);
+
+/// Construct an HTTP bearer (`Authorization: Bearer <token>`) security scheme.
+///
+/// The bearer format is advertised as `JWT`, which is what most consumers of this
+/// crate issue; register it under a name such as `"bearer"` and reference that name
+/// from `#[route(..., security = "bearer")]`.
+#[must_use]
+pub fn bearer_scheme() -> utoipa::openapi::security::SecurityScheme {
+    use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+
+    SecurityScheme::Http(
+        HttpBuilder::new()
+            .scheme(HttpAuthScheme::Bearer)
+            .bearer_format("JWT")
+            .build(),
+    )
+}
+
+/// Construct an API-key security scheme carried in the given request header.
+#[must_use]
+pub fn api_key_scheme(header_name: &str) -> utoipa::openapi::security::SecurityScheme {
+    use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+
+    SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(header_name.to_string())))
+}
+
 /// Helper function to extract all $ref schema names from a schema
 fn extract_schema_refs(schema: &RefOr<utoipa::openapi::schema::Schema>) -> HashSet<String> {
     use utoipa::openapi::schema::Schema;
@@ -67,9 +107,11 @@ fn extract_schema_refs(schema: &RefOr<utoipa::openapi::schema::Schema>) -> HashS
                     }
                 }
             }
-            RefOr::T(Schema::Array(_arr)) => {
-                // Array items are typically inline schemas or simple types
-                // Nested refs will be collected from the parent schema
+            RefOr::T(Schema::Array(arr)) => {
+                use utoipa::openapi::schema::ArrayItems;
+                if let ArrayItems::RefOrSchema(item) = &arr.items {
+                    queue.push_back(item);
+                }
             }
             RefOr::T(Schema::OneOf(one_of)) => {
                 for item in &one_of.items {
@@ -93,6 +135,23 @@ fn extract_schema_refs(schema: &RefOr<utoipa::openapi::schema::Schema>) -> HashS
     refs
 }
 
+/// Build the schema used for binary bodies and responses (file uploads, downloads,
+/// raw byte streams): a `string` with `binary` format.
+///
+/// Used by the `route` macro for `Multipart`/`Bytes` bodies and byte/stream responses,
+/// where there is no `ToSchema` type to describe the payload.
+#[must_use]
+pub fn binary_schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+    use utoipa::openapi::schema::{KnownFormat, ObjectBuilder, SchemaFormat, SchemaType, Type};
+
+    RefOr::T(utoipa::openapi::schema::Schema::Object(
+        ObjectBuilder::new()
+            .schema_type(SchemaType::Type(Type::String))
+            .format(Some(SchemaFormat::KnownFormat(KnownFormat::Binary)))
+            .build(),
+    ))
+}
+
 /// Build an error response for use in `OpenAPI` components.responses
 ///
 /// This is a helper function to create a standard error response that can be
@@ -130,6 +189,53 @@ pub fn build_error_response_from_schema(
         .build()
 }
 
+/// Collect every `#/components/schemas/...` reference contained in a single
+/// operation's request body, parameters, and responses.
+///
+/// Returns `(schema_name, where_referenced)` pairs; the location string is built
+/// from the caller-supplied `location` prefix so callers can point at the exact
+/// operation.
+fn operation_schema_refs(
+    operation: &utoipa::openapi::path::Operation,
+    location: &str,
+) -> Vec<(String, String)> {
+    let mut found = Vec::new();
+
+    let mut scan = |schema: &RefOr<utoipa::openapi::schema::Schema>, detail: &str| {
+        for name in extract_schema_refs(schema) {
+            found.push((name, format!("{location} ({detail})")));
+        }
+    };
+
+    if let Some(request_body) = &operation.request_body {
+        for content in request_body.content.values() {
+            if let Some(schema) = &content.schema {
+                scan(schema, "request body");
+            }
+        }
+    }
+
+    if let Some(parameters) = &operation.parameters {
+        for parameter in parameters {
+            if let Some(schema) = &parameter.schema {
+                scan(schema, "parameter");
+            }
+        }
+    }
+
+    for (status, response) in &operation.responses.responses {
+        if let RefOr::T(response) = response {
+            for content in response.content.values() {
+                if let Some(schema) = &content.schema {
+                    scan(schema, &format!("response {status}"));
+                }
+            }
+        }
+    }
+
+    found
+}
+
 /// Build `OpenAPI` documentation from collected API entries
 #[must_use]
 pub fn build_openapi(title: &str, version: &str) -> OpenApi {
@@ -251,6 +357,13 @@ pub fn build_openapi_with_components<'a>(
 
     components.schemas = schemas;
 
+    // Register every collected security scheme so `components.securitySchemes` is complete.
+    for provider in inventory::iter::<SecuritySchemeProvider> {
+        components
+            .security_schemes
+            .insert(provider.name.to_string(), (provider.scheme)());
+    }
+
     // Add error response if provided
     if let Some((name, response)) = error_response {
         components
@@ -269,3 +382,80 @@ pub fn build_openapi_with_components<'a>(
         .components(Some(components))
         .build()
 }
+
+/// Build `OpenAPI` documentation, failing if any schema reference is left dangling.
+///
+/// This is the strict counterpart to [`build_openapi_with_components`]: it builds the
+/// spec exactly as the lenient variant does (running the same provider-resolution loop),
+/// then performs a final validation pass. It walks every operation's request body,
+/// parameters, and responses plus every schema in `components.schemas`, collects all
+/// referenced `#/components/schemas/...` names, and computes the difference against the
+/// keys actually present. When a referenced schema is missing — typically because no
+/// matching [`SchemaProvider`] was registered — Swagger UI reports
+/// "Could not resolve reference", so catching it at build time is usually what you want.
+///
+/// # Errors
+/// Returns `Err` with a sorted list of messages, one per dangling reference, naming the
+/// missing schema and every place that referenced it.
+pub fn build_openapi_strict<'a>(
+    title: &str,
+    version: &str,
+    additional_schemas: impl IntoIterator<
+        Item = (
+            &'a str,
+            utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+        ),
+    >,
+    error_response: Option<(&'a str, Response)>,
+) -> Result<OpenApi, Vec<String>> {
+    let openapi =
+        build_openapi_with_components(title, version, additional_schemas, error_response);
+
+    let present: HashSet<String> = openapi
+        .components
+        .as_ref()
+        .map(|components| components.schemas.keys().cloned().collect())
+        .unwrap_or_default();
+
+    // Map each dangling schema name to the sorted set of places that referenced it.
+    let mut dangling: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    if let Some(components) = &openapi.components {
+        for (schema_name, schema) in &components.schemas {
+            for name in extract_schema_refs(schema) {
+                if !present.contains(&name) {
+                    dangling
+                        .entry(name)
+                        .or_default()
+                        .insert(format!("components.schemas.{schema_name}"));
+                }
+            }
+        }
+    }
+
+    for (path, path_item) in &openapi.paths.paths {
+        for (method, operation) in &path_item.operations {
+            let location = format!("{method:?} {path}");
+            for (name, reference) in operation_schema_refs(operation, &location) {
+                if !present.contains(&name) {
+                    dangling.entry(name).or_default().insert(reference);
+                }
+            }
+        }
+    }
+
+    if dangling.is_empty() {
+        Ok(openapi)
+    } else {
+        Err(dangling
+            .into_iter()
+            .map(|(name, locations)| {
+                let locations: Vec<String> = locations.into_iter().collect();
+                format!(
+                    "unresolved schema reference `{name}`, referenced by: {}",
+                    locations.join(", ")
+                )
+            })
+            .collect())
+    }
+}
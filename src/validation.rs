@@ -0,0 +1,450 @@
+//! Optional runtime validation of request and response bodies against the generated spec.
+//!
+//! Because this crate owns both the axum routing wiring and the resolved
+//! `components.schemas`, it can check that the hand-written handlers actually conform to
+//! the documented spec. [`ValidationLayer`] is an opt-in tower layer built from the same
+//! [`OpenApi`] value returned by the `build_openapi_*` functions: it validates incoming
+//! JSON request bodies and outgoing JSON responses against the operation's schema,
+//! following `$ref`s into `components.schemas`, and aggregates every violation into a
+//! single [`ErrorResponse`] rather than failing on the first one.
+
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::body::Body;
+use axum::extract::MatchedPath;
+use axum::http::{header, Request, Response, StatusCode};
+use tower::{Layer, Service};
+use utoipa::openapi::schema::Schema;
+use utoipa::openapi::{OpenApi, RefOr};
+
+use crate::ErrorResponse;
+
+/// Whether a value is being validated as an inbound request or outbound response.
+///
+/// The direction decides how `readOnly`/`writeOnly` fields are treated: `readOnly`
+/// fields must not appear in requests and `writeOnly` fields must not appear in
+/// responses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    Request,
+    Response,
+}
+
+/// A single schema violation discovered during validation.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct Violation {
+    /// Dotted JSON path to the offending value (e.g. `food.name`).
+    pub pointer: String,
+    /// Human-readable description of what was wrong.
+    pub message: String,
+}
+
+/// Aggregated validation failure for a single request or response body.
+#[derive(Clone, Debug)]
+pub struct ValidationError {
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationError {
+    fn into_error_response(self) -> ErrorResponse {
+        let detail = self
+            .violations
+            .iter()
+            .map(|v| format!("{}: {}", v.pointer, v.message))
+            .collect::<Vec<_>>()
+            .join("; ");
+        ErrorResponse {
+            error: format!("schema validation failed: {detail}"),
+        }
+    }
+}
+
+/// The request-body and response-body schemas for one operation.
+struct OperationSchemas {
+    request_body: Option<RefOr<Schema>>,
+    responses: BTreeMap<String, RefOr<Schema>>,
+}
+
+/// Flattened, lookup-friendly view of an [`OpenApi`] used during validation.
+struct ValidationIndex {
+    components: BTreeMap<String, RefOr<Schema>>,
+    operations: HashMap<(String, String), OperationSchemas>,
+}
+
+/// Normalize a utoipa [`HttpMethod`](utoipa::openapi::path::HttpMethod) to an uppercase
+/// method string matching [`axum::http::Method`].
+fn method_key(method: &utoipa::openapi::path::HttpMethod) -> String {
+    format!("{method:?}").to_uppercase()
+}
+
+/// Pull the `application/json` schema out of a content map, if present.
+fn json_schema(
+    content: &BTreeMap<String, utoipa::openapi::Content>,
+) -> Option<RefOr<Schema>> {
+    content
+        .get("application/json")
+        .and_then(|c| c.schema.clone())
+}
+
+impl ValidationIndex {
+    fn new(openapi: &OpenApi) -> Self {
+        let components = openapi
+            .components
+            .as_ref()
+            .map(|c| c.schemas.clone())
+            .unwrap_or_default();
+
+        let mut operations = HashMap::new();
+        for (path, item) in &openapi.paths.paths {
+            for (method, operation) in &item.operations {
+                let request_body = operation
+                    .request_body
+                    .as_ref()
+                    .and_then(|rb| json_schema(&rb.content));
+
+                let mut responses = BTreeMap::new();
+                for (status, response) in &operation.responses.responses {
+                    if let RefOr::T(response) = response {
+                        if let Some(schema) = json_schema(&response.content) {
+                            responses.insert(status.clone(), schema);
+                        }
+                    }
+                }
+
+                operations.insert(
+                    (method_key(method), path.clone()),
+                    OperationSchemas {
+                        request_body,
+                        responses,
+                    },
+                );
+            }
+        }
+
+        Self {
+            components,
+            operations,
+        }
+    }
+
+    /// Resolve a `#/components/schemas/...` reference to the underlying schema.
+    fn resolve<'a>(&'a self, schema: &'a RefOr<Schema>) -> Option<&'a Schema> {
+        match schema {
+            RefOr::T(schema) => Some(schema),
+            RefOr::Ref(reference) => {
+                let name = reference
+                    .ref_location
+                    .strip_prefix("#/components/schemas/")?;
+                match self.components.get(name)? {
+                    RefOr::T(schema) => Some(schema),
+                    // One level of indirection is enough for the schemas this crate emits.
+                    RefOr::Ref(_) => None,
+                }
+            }
+        }
+    }
+
+    /// Validate `value` against `schema`, appending any violations to `out`.
+    fn validate(
+        &self,
+        value: &serde_json::Value,
+        schema: &RefOr<Schema>,
+        direction: Direction,
+        pointer: &str,
+        out: &mut Vec<Violation>,
+    ) {
+        let Some(schema) = self.resolve(schema) else {
+            return;
+        };
+
+        if let Schema::Object(object) = schema {
+            use utoipa::openapi::schema::{SchemaType, Type};
+
+            // utoipa models scalar fields (`string`/`integer`/`number`/`bool`) as a
+            // `Schema::Object` with a non-object `schema_type` — see `binary_schema()`
+            // in `lib.rs`. Only enforce the object-map shape when the schema actually
+            // describes an object; scalar schemas get a JSON type check instead.
+            let describes_object = matches!(object.schema_type, SchemaType::Type(Type::Object))
+                || !object.properties.is_empty();
+
+            if !describes_object {
+                if let SchemaType::Type(ty) = &object.schema_type {
+                    // A JSON `null` is tolerated here; presence is governed by `required`.
+                    let matches = value.is_null()
+                        || match ty {
+                            Type::String => value.is_string(),
+                            Type::Integer => value.is_i64() || value.is_u64(),
+                            Type::Number => value.is_number(),
+                            Type::Boolean => value.is_boolean(),
+                            // Object/array are covered by the structural branches.
+                            _ => true,
+                        };
+                    if !matches {
+                        let expected = match ty {
+                            Type::String => "string",
+                            Type::Integer => "integer",
+                            Type::Number => "number",
+                            Type::Boolean => "boolean",
+                            _ => "value",
+                        };
+                        out.push(Violation {
+                            pointer: pointer.to_string(),
+                            message: format!("expected a JSON {expected}"),
+                        });
+                    }
+                }
+                return;
+            }
+
+            let serde_json::Value::Object(map) = value else {
+                out.push(Violation {
+                    pointer: pointer.to_string(),
+                    message: "expected a JSON object".to_string(),
+                });
+                return;
+            };
+
+            // Missing required fields.
+            for required in &object.required {
+                if !map.contains_key(required) {
+                    out.push(Violation {
+                        pointer: join_pointer(pointer, required),
+                        message: "missing required field".to_string(),
+                    });
+                }
+            }
+
+            for (name, property) in &object.properties {
+                let child_pointer = join_pointer(pointer, name);
+
+                if let Some(child) = map.get(name) {
+                    self.check_access(property, direction, &child_pointer, out);
+                    self.validate(child, property, direction, &child_pointer, out);
+                }
+            }
+        } else if let Schema::Array(array) = schema {
+            let serde_json::Value::Array(items) = value else {
+                out.push(Violation {
+                    pointer: pointer.to_string(),
+                    message: "expected a JSON array".to_string(),
+                });
+                return;
+            };
+            use utoipa::openapi::schema::ArrayItems;
+            if let ArrayItems::RefOrSchema(item_schema) = &array.items {
+                for (idx, item) in items.iter().enumerate() {
+                    let child_pointer = format!("{pointer}[{idx}]");
+                    self.validate(item, item_schema, direction, &child_pointer, out);
+                }
+            }
+        }
+    }
+
+    /// Enforce `readOnly`/`writeOnly` access constraints for a property.
+    fn check_access(
+        &self,
+        schema: &RefOr<Schema>,
+        direction: Direction,
+        pointer: &str,
+        out: &mut Vec<Violation>,
+    ) {
+        let Some(Schema::Object(object)) = self.resolve(schema) else {
+            return;
+        };
+        if direction == Direction::Request && object.read_only == Some(true) {
+            out.push(Violation {
+                pointer: pointer.to_string(),
+                message: "readOnly field must not be present in a request".to_string(),
+            });
+        }
+        if direction == Direction::Response && object.write_only == Some(true) {
+            out.push(Violation {
+                pointer: pointer.to_string(),
+                message: "writeOnly field must not be present in a response".to_string(),
+            });
+        }
+    }
+}
+
+/// Join a JSON pointer prefix with a field name.
+fn join_pointer(prefix: &str, field: &str) -> String {
+    if prefix.is_empty() {
+        field.to_string()
+    } else {
+        format!("{prefix}.{field}")
+    }
+}
+
+/// Opt-in tower layer that validates JSON request and response bodies against the spec.
+///
+/// Build it from the same [`OpenApi`] value returned by the `build_openapi_*` functions:
+///
+/// ```ignore
+/// let openapi = build_openapi_with_error_response("food", "1.0.0");
+/// let app = router.layer(ValidationLayer::new(&openapi));
+/// ```
+#[derive(Clone)]
+pub struct ValidationLayer {
+    index: Arc<ValidationIndex>,
+}
+
+impl ValidationLayer {
+    #[must_use]
+    pub fn new(openapi: &OpenApi) -> Self {
+        Self {
+            index: Arc::new(ValidationIndex::new(openapi)),
+        }
+    }
+}
+
+impl<S> Layer<S> for ValidationLayer {
+    type Service = ValidationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ValidationService {
+            inner,
+            index: self.index.clone(),
+        }
+    }
+}
+
+/// The [`Service`] produced by [`ValidationLayer`].
+#[derive(Clone)]
+pub struct ValidationService<S> {
+    inner: S,
+    index: Arc<ValidationIndex>,
+}
+
+/// Build the `400` response carrying the aggregated [`ErrorResponse`].
+fn error_response(error: ValidationError) -> Response<Body> {
+    let body = serde_json::to_vec(&error.into_error_response())
+        .unwrap_or_else(|_| b"{\"error\":\"schema validation failed\"}".to_vec());
+    Response::builder()
+        .status(StatusCode::BAD_REQUEST)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .expect("valid validation error response")
+}
+
+/// Upper bound on a request body we are willing to buffer for validation. Bodies larger
+/// than this are not validated; the request is forwarded with an empty body and left
+/// for the handler to reject.
+const MAX_VALIDATED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// True when the headers advertise a JSON body worth validating.
+fn is_json(headers: &axum::http::HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+impl<S> Service<Request<Body>> for ValidationService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let index = self.index.clone();
+        // Leave a clone in `self` and move the service that was polled ready into the future.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let method = req.method().as_str().to_uppercase();
+            let matched = req
+                .extensions()
+                .get::<MatchedPath>()
+                .map(|m| m.as_str().to_string());
+
+            // Without a matching operation there is nothing to validate against.
+            let Some(path) = matched else {
+                return inner.call(req).await;
+            };
+            let schemas_key = (method, path);
+            if !index.operations.contains_key(&schemas_key) {
+                return inner.call(req).await;
+            }
+
+            let (parts, body) = req.into_parts();
+            // Only buffer the request body when there is actually a JSON schema to check
+            // it against; streaming/binary uploads are passed straight through.
+            let request_schema = if is_json(&parts.headers) {
+                index
+                    .operations
+                    .get(&schemas_key)
+                    .and_then(|op| op.request_body.clone())
+            } else {
+                None
+            };
+
+            let req = if let Some(schema) = request_schema {
+                let bytes = match axum::body::to_bytes(body, MAX_VALIDATED_BODY_BYTES).await {
+                    Ok(bytes) => bytes,
+                    Err(_) => {
+                        // Let the inner service produce its own body error.
+                        let req = Request::from_parts(parts, Body::empty());
+                        return inner.call(req).await;
+                    }
+                };
+                if let Ok(value) = serde_json::from_slice::<serde_json::Value>(&bytes) {
+                    let mut violations = Vec::new();
+                    index.validate(&value, &schema, Direction::Request, "", &mut violations);
+                    if !violations.is_empty() {
+                        return Ok(error_response(ValidationError { violations }));
+                    }
+                }
+                Request::from_parts(parts, Body::from(bytes))
+            } else {
+                Request::from_parts(parts, body)
+            };
+
+            let response = inner.call(req).await?;
+
+            let (parts, body) = response.into_parts();
+            // Validate only when the response's exact status has an indexed JSON schema.
+            // There is deliberately no 200 fallback: error responses (emitted as an
+            // `ErrorResponse` ref) are not indexed, and checking them against the success
+            // type would clobber a genuine error with a spurious validation failure.
+            let response_schema = if is_json(&parts.headers) {
+                index
+                    .operations
+                    .get(&schemas_key)
+                    .and_then(|op| op.responses.get(parts.status.as_str()).cloned())
+            } else {
+                None
+            };
+
+            let Some(schema) = response_schema else {
+                return Ok(Response::from_parts(parts, body));
+            };
+
+            let bytes = match axum::body::to_bytes(body, MAX_VALIDATED_BODY_BYTES).await {
+                Ok(bytes) => bytes,
+                Err(_) => return Ok(Response::from_parts(parts, Body::empty())),
+            };
+
+            if let Ok(value) = serde_json::from_slice::<serde_json::Value>(&bytes) {
+                let mut violations = Vec::new();
+                index.validate(&value, &schema, Direction::Response, "", &mut violations);
+                if !violations.is_empty() {
+                    return Ok(error_response(ValidationError { violations }));
+                }
+            }
+
+            Ok(Response::from_parts(parts, Body::from(bytes)))
+        })
+    }
+}